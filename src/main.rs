@@ -25,71 +25,322 @@
 //! - key: c
 //!   command: cargo clippy --no-deps
 //! ```
+//!
+//! An entry may instead carry a `submenu` key whose value is another list of entries,
+//! built the same way. Selecting it replaces the current menu with the nested one;
+//! press the back key to pop back up a level.
+//!
+//! ```yaml
+//! - key: c
+//!   submenu:
+//!     - cargo build
+//!     - cargo test
+//! ```
+//!
+//! A hash entry may also carry `label` and `description` keys to show a readable
+//! menu instead of the raw command:
+//!
+//! ```yaml
+//! - key: t
+//!   command: cargo test --examples --frozen
+//!   label: run test suite
+//!   description: runs the frozen example suite
+//! ```
+//!
+//! Run `zlelaunch --help` for the full flag listing.
 #![warn(rust_2018_idioms)]
 
 use std::io::{self, Read};
 use std::process::{ExitCode, Termination};
 use std::{env, fs};
-use yaml_rust::{Yaml, YamlLoader};
+use yaml_rust::yaml::Hash;
+use yaml_rust::{Yaml, YamlEmitter, YamlLoader};
+
+/// Key that pops back up one level from a submenu
+const BACK_KEY: char = '-';
+
+/// What happens when an entry's key is pressed
+enum LauncherAction<'a> {
+    /// Print this shell command to stdout
+    Command(&'a str),
+    /// Replace the current menu with a nested one
+    Submenu(Vec<LauncherEntry<'a>>),
+}
 
 /// An entry in the launcher menu
 struct LauncherEntry<'a> {
     /// The key for executing the command
     character: Option<char>,
-    /// A shell command
-    command: &'a str,
+    /// Menu text to show instead of the raw command/submenu marker
+    label: Option<&'a str>,
+    /// Extra text shown dimmed on an indented line below the label
+    description: Option<&'a str>,
+    /// What pressing the key does
+    action: LauncherAction<'a>,
 }
 
 impl<'a> LauncherEntry<'a> {
-    fn new(command: &'a str) -> Self {
+    fn command(command: &'a str) -> Self {
+        LauncherEntry {
+            character: None,
+            label: None,
+            description: None,
+            action: LauncherAction::Command(command),
+        }
+    }
+
+    fn submenu(entries: Vec<LauncherEntry<'a>>) -> Self {
         LauncherEntry {
             character: None,
-            command,
+            label: None,
+            description: None,
+            action: LauncherAction::Submenu(entries),
         }
     }
 }
 
-/// Display menu on stderr
-fn output(entries: &[LauncherEntry<'_>]) {
-    // hide cursor
-    eprint!("\x1b[?25l");
-    // count number of newlines, these will be erased after a key is pressed
-    let mut linecount = 0;
-    entries.iter().for_each(|e| {
-        linecount += 1;
-        let mut command = String::new();
-        e.command.chars().for_each(|ch| {
-            match ch {
-                c if c == '\n' => {
-                    linecount += 1;
-                    // indent each line to match the first one
-                    command.push_str("\n    ");
+/// Indent continuation lines of multi-line text to match the first line,
+/// bumping `linecount` for each extra line introduced
+fn indent_lines(text: &str, linecount: &mut usize) -> String {
+    let mut rendered = String::new();
+    text.chars().for_each(|ch| match ch {
+        '\n' => {
+            *linecount += 1;
+            rendered.push_str("\n    ");
+        }
+        c => rendered.push(c),
+    });
+    rendered
+}
+
+/// Display menu on stderr, descending into submenus and printing the chosen
+/// command once a leaf entry is picked (to stderr if `dry_run`, else
+/// stdout). `nested` controls whether a "back" entry is offered to pop back
+/// up to the caller. `select`, if given, is used as the first keypress
+/// instead of reading one from stdin, for non-interactive use.
+///
+/// Returns whether a command was executed, so a caller whose submenu call
+/// returns `true` can stop reading keys itself instead of redrawing and
+/// blocking on a second keypress that may never come. `reader` supplies
+/// keypresses once `select` is exhausted (real stdin in `main`, a fixed
+/// buffer in tests).
+fn output<R: Read>(
+    entries: &[LauncherEntry<'_>],
+    nested: bool,
+    dry_run: bool,
+    mut select: Option<char>,
+    reader: &mut R,
+) -> bool {
+    loop {
+        // hide cursor
+        eprint!("\x1b[?25l");
+        // count number of newlines, these will be erased after a key is pressed
+        let mut linecount = 0;
+        entries.iter().for_each(|e| {
+            linecount += 1;
+            let text = match e.label {
+                Some(label) => label,
+                None => match &e.action {
+                    LauncherAction::Command(command) => command,
+                    LauncherAction::Submenu(_) => "submenu",
+                },
+            };
+            let mut rendered = indent_lines(text, &mut linecount);
+            if let Some(description) = e.description {
+                linecount += 1;
+                rendered.push_str("\n    \x1b[2m");
+                rendered.push_str(&indent_lines(description, &mut linecount));
+                rendered.push_str("\x1b[0m");
+            }
+            eprint!(
+                "\n \x1b[33m\x1b[1m{}\x1b[0m {}",
+                e.character.unwrap(),
+                rendered,
+            )
+        });
+        if nested {
+            linecount += 1;
+            eprint!("\n \x1b[33m\x1b[1m{BACK_KEY}\x1b[0m back");
+        }
+        let k = match select.take() {
+            Some(c) => c,
+            None => {
+                let mut buffer = [0u8; 1];
+                match reader.read_exact(&mut buffer) {
+                    Ok(_) => buffer[0] as char,
+                    Err(_) => panic!("Could not read key from stdin"),
                 }
-                c => command.push(c),
             }
+        };
+        // erase the menu (an empty entry list with no back entry never printed
+        // a line, so there's nothing above the cursor to erase)
+        (0..linecount.saturating_sub(1)).for_each(|_| {
+            eprint!("\x1b[2K\x1b[F");
         });
-        eprint!(
-            "\n \x1b[33m\x1b[1m{}\x1b[0m {}",
-            e.character.unwrap(),
-            command,
-        )
+        // erase last line, move to column 1 and show cursor
+        eprint!("\x1b[2K\x1b[G\x1b[?25h");
+        if nested && k == BACK_KEY {
+            return false;
+        }
+        match entries.iter().find(|e| e.character == Some(k)) {
+            Some(entry) => match &entry.action {
+                LauncherAction::Command(command) => {
+                    if dry_run {
+                        eprintln!("{command}");
+                    } else {
+                        println!("{command}");
+                    }
+                    return true;
+                }
+                LauncherAction::Submenu(sub_entries) => {
+                    if output(sub_entries, true, dry_run, select.take(), reader) {
+                        return true;
+                    }
+                    // the submenu was backed out of; redraw this level and read again
+                }
+            },
+            None => return false,
+        }
+    }
+}
+
+/// Recursively print every leaf command, null-separated
+fn print0(entries: &[LauncherEntry<'_>]) {
+    entries.iter().for_each(|entry| match &entry.action {
+        LauncherAction::Command(command) => print!("{command}\0"),
+        LauncherAction::Submenu(sub_entries) => print0(sub_entries),
     });
-    let mut buffer = [0u8; 1];
-    let k = match io::stdin().read_exact(&mut buffer) {
-        Ok(_) => buffer[0] as char,
-        Err(_) => panic!("Could not read key from stdin"),
+}
+
+/// Build the text for a fresh config file: a couple of commented-out example
+/// entries and nothing else, so the file as written holds no YAML document
+/// (it loads as no entries) — uncommenting the examples turns them straight
+/// into a single document's array instead of leaving a second,
+/// separately-emitted empty array behind them
+fn scaffold_config() -> String {
+    "# zlelaunch config: each entry is a shell command, or a hash with\n\
+     # \"key\" (character to press) and \"command\" (what to run).\n\
+     # Uncomment to get started, or add your own with `zlelaunch --add`.\n\
+     #\n\
+     # - cargo test --examples --frozen\n\
+     # - key: c\n\
+     #   command: cargo clippy --no-deps\n"
+        .to_string()
+}
+
+/// Load the existing document at `filename` (or start a fresh array if it
+/// doesn't exist), append a new entry for `command`/`key`, and write the
+/// whole document back with `YamlEmitter`
+///
+/// Only a single top-level `Yaml::Array` document is supported: a file
+/// containing anything else (a stray hash, several `---`-separated
+/// documents, ...) is left untouched and an error is returned instead of
+/// silently discarding it.
+fn add_entry(filename: &str, command: &str, key: Option<char>) -> io::Result<()> {
+    let existing = fs::read_to_string(filename).ok();
+    let docs = match &existing {
+        Some(s) => YamlLoader::load_from_str(s).unwrap(),
+        None => Vec::new(),
     };
-    // erase the menu
-    (0..linecount - 1).for_each(|_| {
-        eprint!("\x1b[2K\x1b[F");
+    if docs.len() > 1 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "config has multiple YAML documents; --add only supports a single top-level array",
+        ));
+    }
+    let mut array = match docs.into_iter().next() {
+        Some(Yaml::Array(a)) => a,
+        Some(Yaml::Null) | None => Vec::new(),
+        Some(other) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("expected a top-level array, found {other:?}"),
+            ));
+        }
+    };
+    array.push(match key {
+        Some(c) => {
+            let mut hash = Hash::new();
+            hash.insert(Yaml::String("key".to_string()), Yaml::String(c.to_string()));
+            hash.insert(
+                Yaml::String("command".to_string()),
+                Yaml::String(command.to_string()),
+            );
+            Yaml::Hash(hash)
+        }
+        None => Yaml::String(command.to_string()),
     });
-    // erase last line, move to column 1 and show cursor
-    eprint!("\x1b[2K\x1b[G\x1b[?25h");
-    entries.iter().for_each(|e| {
-        if k == e.character.unwrap() {
-            println!("{}", e.command);
+
+    let mut out = String::new();
+    {
+        let mut emitter = YamlEmitter::new(&mut out);
+        emitter.dump(&Yaml::Array(array)).unwrap();
+    }
+    out.push('\n');
+    fs::write(filename, out)
+}
+
+/// Parse a single level of entries, reserving `seed_keys` (e.g. `z` for the
+/// top-level edit entry, or the back key for a submenu) before any keys are
+/// auto-assigned
+///
+/// returns the entries at this level and the keys reserved within it
+fn parse_entries<'a>(entries: &'a [Yaml], seed_keys: &[char]) -> (Vec<LauncherEntry<'a>>, Vec<char>) {
+    let mut launcher_entries: Vec<_> = Vec::new();
+    let mut reserved_keys: Vec<_> = seed_keys.to_vec();
+
+    let key_command = &Yaml::String("command".to_string());
+    let key_key = &Yaml::String("key".to_string());
+    let key_submenu = &Yaml::String("submenu".to_string());
+    let key_label = &Yaml::String("label".to_string());
+    let key_description = &Yaml::String("description".to_string());
+
+    entries.iter().enumerate().for_each(|(idx, entry)| match entry {
+        Yaml::Hash(hash_entry) => {
+            let explicit_key = hash_entry
+                .get(key_key)
+                .map(|key| key.as_str().unwrap().chars().next().unwrap());
+
+            let mut launcher_entry = match hash_entry.get(key_submenu) {
+                Some(Yaml::Array(submenu)) => {
+                    let (mut sub_entries, sub_keys) = parse_entries(submenu, &[BACK_KEY]);
+                    assign_keys(&mut sub_entries, &sub_keys);
+                    LauncherEntry::submenu(sub_entries)
+                }
+                Some(value) => {
+                    eprintln!("Expected array for \"submenu\" at index {idx}, found: {value:?}");
+                    return;
+                }
+                None => match hash_entry.get(key_command) {
+                    Some(value) => {
+                        let command = value.as_str();
+                        LauncherEntry::command(command.unwrap())
+                    }
+                    None => {
+                        eprintln!(
+                            "Missing required key \"command\" at index {idx}, found {entry:?}"
+                        );
+                        return;
+                    }
+                },
+            };
+            if let Some(c) = explicit_key {
+                if !reserved_keys.contains(&c) {
+                    launcher_entry.character = Some(c);
+                    reserved_keys.push(c);
+                }
+            }
+            launcher_entry.label = hash_entry.get(key_label).and_then(|v| v.as_str());
+            launcher_entry.description = hash_entry.get(key_description).and_then(|v| v.as_str());
+            launcher_entries.push(launcher_entry);
+        }
+        Yaml::String(string_entry) => {
+            let command = string_entry.as_str();
+            launcher_entries.push(LauncherEntry::command(command));
         }
+        _ => panic!("Expected string or mapping at index {idx}, found: {entry:?}"),
     });
+    (launcher_entries, reserved_keys)
 }
 
 /// Parse yaml documents
@@ -99,39 +350,14 @@ fn parse_yaml(docs: &[Yaml]) -> (Vec<LauncherEntry<'_>>, Vec<char>) {
     let mut launcher_entries: Vec<_> = Vec::new();
     let mut reserved_keys: Vec<_> = vec!['z'];
 
-    let key_command = &Yaml::String("command".to_string());
-    let key_key = &Yaml::String("key".to_string());
-
     docs.iter().for_each(|doc| {
         if let Yaml::Array(e) = doc {
-            e.iter().enumerate().for_each(|(idx, entry)| match entry {
-                Yaml::Hash(hash_entry) => {
-                    match hash_entry.get(key_command) {
-                        Some(value) => {
-                            let command = value.as_str();
-                            let mut entry = LauncherEntry::new(command.unwrap());
-                            if let Some(key) = hash_entry.get(key_key) {
-                                let c = key.as_str().unwrap().chars().next().unwrap();
-                                if !reserved_keys.contains(&c) {
-                                    entry.character = Some(c);
-                                    reserved_keys.push(c);
-                                }
-                            }
-                            launcher_entries.push(entry);
-                        }
-                        None => {
-                            eprintln!(
-                                "Missing required key \"command\" at index {idx}, found {entry:?}"
-                            )
-                        }
-                    };
-                    // let command = hash_entry[key_command].as_str();
-                }
-                Yaml::String(string_entry) => {
-                    let command = string_entry.as_str();
-                    launcher_entries.push(LauncherEntry::new(command));
+            let (mut entries, keys) = parse_entries(e, &reserved_keys);
+            launcher_entries.append(&mut entries);
+            keys.into_iter().for_each(|k| {
+                if !reserved_keys.contains(&k) {
+                    reserved_keys.push(k);
                 }
-                _ => panic!("Expected string or mapping at index {idx}, found: {entry:?}"),
             });
         } else {
             panic!("Expected array, found {doc:?}");
@@ -155,13 +381,150 @@ fn assign_keys(entries: &mut [LauncherEntry<'_>], reserved_keys: &[char]) {
     });
 }
 
+/// A single command-line flag as listed by `--help`; `parse_args` matches the
+/// same flag names itself and does not read this table
+struct Flag {
+    name: &'static str,
+    value: Option<&'static str>,
+    help: &'static str,
+}
+
+const FLAGS: &[Flag] = &[
+    Flag {
+        name: "--print0",
+        value: None,
+        help: "walk the whole tree and emit every leaf command null-separated",
+    },
+    Flag {
+        name: "--dry-run",
+        value: None,
+        help: "render the menu and echo the chosen command to stderr instead of stdout",
+    },
+    Flag {
+        name: "--editor",
+        value: Some("<cmd>"),
+        help: "override $EDITOR (falls back to vim) for the injected edit entry",
+    },
+    Flag {
+        name: "--no-edit",
+        value: None,
+        help: "suppress the injected \"edit config\" entry",
+    },
+    Flag {
+        name: "--select",
+        value: Some("<char>"),
+        help: "pre-choose a key instead of reading one from stdin",
+    },
+    Flag {
+        name: "--add",
+        value: Some("<command>"),
+        help: "append <command> to the config and exit",
+    },
+    Flag {
+        name: "--key",
+        value: Some("<char>"),
+        help: "key to assign the entry added with --add",
+    },
+    Flag {
+        name: "--help",
+        value: None,
+        help: "print this listing and exit",
+    },
+];
+
+/// Parsed command-line configuration
+struct Config {
+    path: String,
+    print0: bool,
+    dry_run: bool,
+    editor: Option<String>,
+    no_edit: bool,
+    select: Option<char>,
+    add: Option<String>,
+    key: Option<char>,
+}
+
+/// Result of parsing the command line
+enum Args {
+    Help,
+    Config(Config),
+}
+
+/// Print the `--help` listing
+fn print_help() {
+    eprintln!("usage: zlelaunch <path> [flags]");
+    eprintln!();
+    FLAGS.iter().for_each(|flag| {
+        let name = match flag.value {
+            Some(value) => format!("{} {value}", flag.name),
+            None => flag.name.to_string(),
+        };
+        eprintln!("    {name:<24} {}", flag.help);
+    });
+}
+
+/// Parse `args` (excluding argv[0]) into a [`Config`], xflags-style: one
+/// positional config path plus the typed options in [`FLAGS`]
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut path = None;
+    let mut print0 = false;
+    let mut dry_run = false;
+    let mut editor = None;
+    let mut no_edit = false;
+    let mut select = None;
+    let mut add = None;
+    let mut key = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--help" => return Ok(Args::Help),
+            "--print0" => print0 = true,
+            "--dry-run" => dry_run = true,
+            "--no-edit" => no_edit = true,
+            "--editor" => editor = Some(args.next().ok_or("--editor requires a value")?),
+            "--select" => {
+                let value = args.next().ok_or("--select requires a value")?;
+                select = Some(
+                    value
+                        .chars()
+                        .next()
+                        .ok_or("--select requires a single character")?,
+                );
+            }
+            "--add" => add = Some(args.next().ok_or("--add requires a value")?),
+            "--key" => {
+                let value = args.next().ok_or("--key requires a value")?;
+                key = Some(
+                    value
+                        .chars()
+                        .next()
+                        .ok_or("--key requires a single character")?,
+                );
+            }
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(format!("unexpected argument: {arg}")),
+        }
+    }
+
+    Ok(Args::Config(Config {
+        path: path.ok_or("missing required argument <path>")?,
+        print0,
+        dry_run,
+        editor,
+        no_edit,
+        select,
+        add,
+        key,
+    }))
+}
+
 /// Program exit status
-enum Exit<'life> {
+enum Exit {
     Ok,
-    ErrorMessage(&'life str),
+    ErrorMessage(String),
 }
 
-impl<'life> Termination for Exit<'life> {
+impl Termination for Exit {
     fn report(self) -> ExitCode {
         ExitCode::from(match self {
             Exit::Ok => 0,
@@ -174,48 +537,64 @@ impl<'life> Termination for Exit<'life> {
 }
 
 /// Entrypoint
-fn main() -> Exit<'static> {
-    let (filename, print0) = {
-        let mut filename = None;
-        let mut print0 = false;
-        env::args().for_each(|arg| {
-            if arg == "--print0" {
-                print0 = true;
-            } else {
-                filename = Some(arg);
-            }
-        });
-        if filename.is_none() {
-            return Exit::ErrorMessage("missing filename argument");
+fn main() -> Exit {
+    let config = match parse_args(env::args().skip(1)) {
+        Ok(Args::Help) => {
+            print_help();
+            return Exit::Ok;
+        }
+        Ok(Args::Config(config)) => config,
+        Err(message) => {
+            print_help();
+            return Exit::ErrorMessage(message);
         }
-        (filename.unwrap(), print0)
     };
-    let file_result = fs::read_to_string(&filename);
+
+    if let Some(command) = &config.add {
+        return match add_entry(&config.path, command, config.key) {
+            Ok(()) => Exit::Ok,
+            Err(e) => Exit::ErrorMessage(format!("failed to update config: {e}")),
+        };
+    }
+
+    let file_result = fs::read_to_string(&config.path);
     let yaml = match file_result {
         Ok(y) => YamlLoader::load_from_str(&y).unwrap(),
         Err(_) => {
             eprintln!("Failed to read file, create?");
-            Vec::new()
+            let starter = scaffold_config();
+            if fs::write(&config.path, &starter).is_err() {
+                return Exit::ErrorMessage("failed to write starter config".to_string());
+            }
+            YamlLoader::load_from_str(&starter).unwrap()
         }
     };
     let (mut entries, keys) = parse_yaml(&yaml);
-    let editor = match env::var("EDITOR") {
-        Ok(s) => s,
-        _ => "vim".to_string(),
+
+    let edit_command = if config.no_edit {
+        None
+    } else {
+        let editor = config
+            .editor
+            .clone()
+            .unwrap_or_else(|| env::var("EDITOR").unwrap_or_else(|_| "vim".to_string()));
+        Some(format!("{editor} {path}", path = config.path))
     };
-    let edit_command = format!("{editor} {filename}");
-    entries.push(LauncherEntry {
-        character: Some('z'),
-        command: &edit_command,
-    });
-    if print0 {
-        // Ignore keys and just print every command null-separated
-        entries
-            .into_iter()
-            .for_each(|entry| print!("{}\0", entry.command));
+    if let Some(ref edit_command) = edit_command {
+        entries.push(LauncherEntry {
+            character: Some('z'),
+            label: None,
+            description: None,
+            action: LauncherAction::Command(edit_command),
+        });
+    }
+
+    if config.print0 {
+        // Ignore keys and just print every leaf command null-separated
+        print0(&entries);
     } else {
         assign_keys(&mut entries, &keys);
-        output(&entries);
+        output(&entries, false, config.dry_run, config.select, &mut io::stdin().lock());
     }
     Exit::Ok
 }
@@ -232,12 +611,21 @@ mod tests {
   command: echo hej
 ";
 
+    const YAML_SUBMENU: &str = "
+- python test.py
+- key: c
+  submenu:
+    - cargo build
+    - key: a
+      command: cargo test
+";
+
     #[test]
     fn test_string_entry() {
         let yaml = YamlLoader::load_from_str(YAML).unwrap();
         let (entries, _) = parse_yaml(&yaml);
         // test string entry
-        assert_eq!(entries[0].command, "python test.py");
+        assert!(matches!(entries[0].action, LauncherAction::Command("python test.py")));
         assert_eq!(entries[0].character, None);
     }
 
@@ -246,7 +634,7 @@ mod tests {
         let yaml = YamlLoader::load_from_str(YAML).unwrap();
         let (entries, _) = parse_yaml(&yaml);
         // test hash entry
-        assert_eq!(entries[1].command, "pytest -s");
+        assert!(matches!(entries[1].action, LauncherAction::Command("pytest -s")));
         assert_eq!(entries[1].character, Some('a'));
     }
 
@@ -266,4 +654,189 @@ mod tests {
         assert_eq!(entries[0].character, Some('o'));
         assert_eq!(entries[1].character, Some('a'));
     }
+
+    #[test]
+    fn test_submenu_entry() {
+        let yaml = YamlLoader::load_from_str(YAML_SUBMENU).unwrap();
+        let (entries, _) = parse_yaml(&yaml);
+        match &entries[1].action {
+            LauncherAction::Submenu(sub_entries) => {
+                assert_eq!(sub_entries.len(), 2);
+                assert!(matches!(sub_entries[0].action, LauncherAction::Command("cargo build")));
+                // keys inside a submenu are assigned independently of the parent level
+                assert_eq!(sub_entries[0].character, Some('o'));
+                assert_eq!(sub_entries[1].character, Some('a'));
+            }
+            _ => panic!("expected a submenu entry"),
+        }
+    }
+
+    #[test]
+    fn test_select_into_submenu_stops_after_command() {
+        let yaml = YamlLoader::load_from_str(YAML_SUBMENU).unwrap();
+        let (mut entries, keys) = parse_yaml(&yaml);
+        assign_keys(&mut entries, &keys);
+        // `--select c` enters the submenu; the single `a` byte picks the command
+        // inside it. A real zle pipe has nothing left to read after that, so
+        // `output` must return instead of looping back to read a second key.
+        let mut reader = std::io::Cursor::new(vec![b'a']);
+        let executed = output(&entries, false, false, Some('c'), &mut reader);
+        assert!(executed);
+    }
+
+    #[test]
+    fn test_output_with_no_entries_does_not_panic() {
+        // a freshly-scaffolded config loaded with --no-edit (so no 'z' entry
+        // is appended) has an empty entry list; `linecount` stays 0 and the
+        // erase loop in `output` must not underflow trying to subtract 1 from it
+        let mut reader = std::io::Cursor::new(vec![b'x']);
+        let executed = output(&[], false, false, None, &mut reader);
+        assert!(!executed);
+    }
+
+    const YAML_LABEL: &str = "
+- key: t
+  command: cargo test --examples --frozen
+  label: run test suite
+  description: runs the frozen example suite
+";
+
+    #[test]
+    fn test_label_and_description() {
+        let yaml = YamlLoader::load_from_str(YAML_LABEL).unwrap();
+        let (entries, _) = parse_yaml(&yaml);
+        assert!(matches!(
+            entries[0].action,
+            LauncherAction::Command("cargo test --examples --frozen")
+        ));
+        assert_eq!(entries[0].label, Some("run test suite"));
+        assert_eq!(entries[0].description, Some("runs the frozen example suite"));
+    }
+
+    /// A path under the system temp dir, unique to this test run
+    fn temp_config_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("zlelaunch-test-{}-{name}.yml", std::process::id()))
+    }
+
+    #[test]
+    fn test_scaffold_config_parses_to_no_entries() {
+        let docs = YamlLoader::load_from_str(&scaffold_config()).unwrap();
+        let (entries, _) = parse_yaml(&docs);
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn test_scaffold_config_examples_uncomment_into_one_document() {
+        // stripping the leading "# " from each example line (but leaving the
+        // descriptive header comments alone) should turn the scaffold into a
+        // single parseable document, not a second document trailing behind
+        // the commented-out one
+        let uncommented: String = scaffold_config()
+            .lines()
+            .map(|line| match line.strip_prefix("# ") {
+                Some(rest) if rest.starts_with('-') || rest.starts_with(' ') => rest,
+                _ => line,
+            })
+            .map(|line| format!("{line}\n"))
+            .collect();
+
+        let path = temp_config_path("scaffold-uncommented");
+        fs::write(&path, uncommented).unwrap();
+
+        add_entry(path.to_str().unwrap(), "cargo test", Some('t')).unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        let docs = YamlLoader::load_from_str(&contents).unwrap();
+        assert_eq!(docs.len(), 1);
+        let (entries, _) = parse_yaml(&docs);
+        assert!(matches!(
+            entries[0].action,
+            LauncherAction::Command("cargo test --examples --frozen")
+        ));
+        assert!(matches!(entries[2].action, LauncherAction::Command("cargo test")));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_add_entry_appends_to_existing_array() {
+        let path = temp_config_path("add-existing");
+        fs::write(&path, "- cargo build\n").unwrap();
+
+        add_entry(path.to_str().unwrap(), "cargo test", Some('t')).unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        let docs = YamlLoader::load_from_str(&contents).unwrap();
+        let (entries, _) = parse_yaml(&docs);
+        assert!(matches!(entries[0].action, LauncherAction::Command("cargo build")));
+        assert!(matches!(entries[1].action, LauncherAction::Command("cargo test")));
+        assert_eq!(entries[1].character, Some('t'));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_add_entry_rejects_non_array_document() {
+        let path = temp_config_path("add-non-array");
+        fs::write(&path, "foo: bar\n").unwrap();
+
+        assert!(add_entry(path.to_str().unwrap(), "echo x", None).is_err());
+        // the original, malformed-for-us content must be left untouched
+        assert_eq!(fs::read_to_string(&path).unwrap(), "foo: bar\n");
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    fn args(strs: &[&str]) -> impl Iterator<Item = String> {
+        strs.iter().map(|s| s.to_string()).collect::<Vec<_>>().into_iter()
+    }
+
+    #[test]
+    fn test_parse_args_defaults() {
+        match parse_args(args(&[".ctrl_e.yml"])).unwrap() {
+            Args::Config(config) => {
+                assert_eq!(config.path, ".ctrl_e.yml");
+                assert!(!config.print0);
+                assert!(!config.dry_run);
+                assert!(!config.no_edit);
+                assert_eq!(config.select, None);
+            }
+            Args::Help => panic!("expected a config"),
+        }
+    }
+
+    #[test]
+    fn test_parse_args_options() {
+        match parse_args(args(&[
+            ".ctrl_e.yml",
+            "--print0",
+            "--dry-run",
+            "--no-edit",
+            "--editor",
+            "nano",
+            "--select",
+            "a",
+        ]))
+        .unwrap()
+        {
+            Args::Config(config) => {
+                assert!(config.print0);
+                assert!(config.dry_run);
+                assert!(config.no_edit);
+                assert_eq!(config.editor, Some("nano".to_string()));
+                assert_eq!(config.select, Some('a'));
+            }
+            Args::Help => panic!("expected a config"),
+        }
+    }
+
+    #[test]
+    fn test_parse_args_missing_path() {
+        assert!(parse_args(args(&["--print0"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_help() {
+        assert!(matches!(parse_args(args(&["--help"])).unwrap(), Args::Help));
+    }
 }